@@ -7,6 +7,27 @@
 //! [autoref based specialization](https://lukaskalbertodt.github.io/2019/12/05/generalized-autoref-based-specialization.html)
 //! e.g. a `PathBuf` will be created via `From<OsString>` not `From<String>` so non UTF8 paths
 //! will work.
+//!
+//! With the `camino` feature enabled, `camino::Utf8Path`/`Utf8PathBuf` are also supported as
+//! targets. Since those types guarantee their contents are valid UTF8 they are built via the
+//! same UTF8 check as `String`/`&str`, returning [`Error::Utf8`] for non UTF8 input rather than
+//! falling back to a lossy `PathBuf` conversion.
+//!
+//! Byte oriented targets such as `Vec<u8>` and `bstr::BString` are handled losslessly on every
+//! platform: on Unix the bytes are the `OsStr`'s bytes as-is, and on Windows they are the WTF-8
+//! encoding of the (possibly ill-formed) UTF-16, so even non UTF8 input converts successfully.
+//!
+//! For callers who would rather get a best effort string than an [`Error::Utf8`], there is also
+//! [`from_os_str_lossy!`], which behaves the same as [`try_from_os_str!`] except that owned
+//! string targets (e.g. `String`, or anything parsed via `FromStr`) are built from
+//! [`OsStr::to_string_lossy`] instead of failing on non UTF8 input. Targets converted via
+//! `TryFrom<&str>`/`From<&str>`, such as `&str` itself, still return [`Error::Utf8`] for non
+//! UTF8 input.
+//!
+//! `Cow<'a, str>` is also a supported target: it borrows straight from the input with no
+//! allocation when the input is valid UTF8, and only allocates (falling back to the lossy
+//! behaviour above under [`from_os_str_lossy!`]) when it isn't. `Cow<'a, OsStr>` works too, and
+//! always borrows since it never needs to check UTF8 validity at all.
 //! ```
 //! # #[macro_use] extern crate from_os_str;
 //! # fn main() {
@@ -22,6 +43,7 @@
 //! ```
 
 use std::{
+    borrow::Cow,
     convert::Infallible,
     error::Error as StdError,
     ffi::{OsStr, OsString},
@@ -31,13 +53,70 @@ use std::{
     str::FromStr,
 };
 
+#[cfg(feature = "camino")]
+use camino::{Utf8Path, Utf8PathBuf};
+
+#[cfg(windows)]
+mod wtf8;
+
+/// Borrow the bytes of an `OsStr` with zero copies.
+///
+/// Only available on Unix: on Windows an `OsStr` is ill-formed UTF-16 and getting its bytes
+/// losslessly means transcoding to WTF-8, which can't be done without allocating.
+#[cfg(unix)]
+fn os_str_as_bytes(s: &OsStr) -> &[u8] {
+    use std::os::unix::ffi::OsStrExt;
+    s.as_bytes()
+}
+
+/// Get the bytes of an `OsStr` losslessly, on every platform.
+///
+/// On Unix this is just the `OsStr`'s underlying bytes. On Windows, where an `OsStr` is
+/// ill-formed UTF-16, the bytes are its WTF-8 encoding (see [`os_string_from_bytes`] for the
+/// inverse).
+#[cfg(unix)]
+fn os_str_to_bytes(s: &OsStr) -> Vec<u8> {
+    os_str_as_bytes(s).to_vec()
+}
+
+/// Get the bytes of an `OsStr` losslessly, on every platform.
+///
+/// On Unix this is just the `OsStr`'s underlying bytes. On Windows, where an `OsStr` is
+/// ill-formed UTF-16, the bytes are its WTF-8 encoding (see [`os_string_from_bytes`] for the
+/// inverse).
+#[cfg(windows)]
+fn os_str_to_bytes(s: &OsStr) -> Vec<u8> {
+    use std::os::windows::ffi::OsStrExt;
+    wtf8::encode(s.encode_wide())
+}
+
+/// Losslessly reconstruct an `OsString` from bytes produced by [`os_str_to_bytes`] (or, on
+/// Unix, any other source of raw path/filename bytes; on Windows the bytes must be valid
+/// WTF-8).
+#[cfg(unix)]
+pub fn os_string_from_bytes(bytes: &[u8]) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    OsString::from_vec(bytes.to_vec())
+}
+
+/// Losslessly reconstruct an `OsString` from bytes produced by [`os_str_to_bytes`] (or, on
+/// Unix, any other source of raw path/filename bytes; on Windows the bytes must be valid
+/// WTF-8).
+#[cfg(windows)]
+pub fn os_string_from_bytes(bytes: &[u8]) -> OsString {
+    use std::os::windows::ffi::OsStringExt;
+    OsString::from_wide(&wtf8::decode(bytes))
+}
+
 /// An error that can occure when converting an OsString to another type
 /// It can either be a problem converting the bytes passed into the OsStr
 /// as a valid UTF8 string or an error parsing the string
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error<T> {
-    /// The OsStr contains bytes that are not valid UTF8
-    Utf8,
+    /// The OsStr contains bytes that are not valid UTF8. The offending input is kept around for
+    /// diagnostics, since the string it would have produced usually needs to appear somewhere in
+    /// the error message the caller shows.
+    Utf8(OsString),
     /// Parsing the string failed
     ParseErr(T),
 }
@@ -45,7 +124,7 @@ pub enum Error<T> {
 impl<T: Display> Display for Error<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::Utf8 => write!(f, "invalid utf-8 sequence"),
+            Error::Utf8(s) => write!(f, "invalid utf-8 sequence: {}", s.to_string_lossy()),
             Error::ParseErr(err) => err.fmt(f),
         }
     }
@@ -54,7 +133,7 @@ impl<T: Display> Display for Error<T> {
 impl<T: StdError + 'static> StdError for Error<T> {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
-            Error::Utf8 => None,
+            Error::Utf8(_) => None,
             Error::ParseErr(err) => Some(err),
         }
     }
@@ -75,13 +154,53 @@ macro_rules! specialize {
     (impl ($($and:tt)+) $name:ident for $from_ty:path {
         fn from_str($s:ident: &str) -> Result<T, $err:ty> {$($body:tt)*}
     }) => {
-        specialize! {
-            impl ($($and)+) $name for $from_ty {
-                fn specialized(&self) -> Result<T, Error<$err>> {
-                    match self.0.to_str() {
-                        None => Err(Error::Utf8),
-                        Some($s) => {$($body)*}.map_err(Error::ParseErr),
-                    }
+        #[doc(hidden)]
+        pub trait $name {
+            type Return;
+            fn specialized(&self) -> Self::Return;
+            // The lossy macro feeds `to_string_lossy()`'s output through the same body instead
+            // of rejecting non UTF8 input, so it needs its own override here.
+            fn specialized_lossy(&self) -> Self::Return;
+        }
+
+        impl<'a, T: $from_ty> $name for $($and)+Wrap<'a, T> {
+            type Return = Result<T, Error<$err>>;
+            fn specialized(&self) -> Self::Return {
+                match self.0.to_str() {
+                    None => Err(Error::Utf8(self.0.to_os_string())),
+                    Some($s) => {$($body)*}.map_err(Error::ParseErr),
+                }
+            }
+            fn specialized_lossy(&self) -> Self::Return {
+                let lossy = self.0.to_string_lossy();
+                let $s: &str = &lossy;
+                {$($body)*}.map_err(Error::ParseErr)
+            }
+        }
+    };
+    // Like `from_str`, but for a `$from_ty` bound that borrows with the outer `'a` (e.g.
+    // `From<&'a str>`), rather than an owned one (e.g. `From<String>`). The target may itself
+    // be a zero-copy borrow (plain `&str` being the main example), so under the lossy macro a
+    // replaced, allocated string wouldn't live long enough to borrow from - this tier stays
+    // strict even there.
+    (impl ($($and:tt)+) $name:ident for $from_ty:path {
+        fn from_str_ref($s:ident: &str) -> Result<T, $err:ty> {$($body:tt)*}
+    }) => {
+        #[doc(hidden)]
+        pub trait $name {
+            type Return;
+            fn specialized(&self) -> Self::Return;
+            fn specialized_lossy(&self) -> Self::Return {
+                self.specialized()
+            }
+        }
+
+        impl<'a, T: $from_ty> $name for $($and)+Wrap<'a, T> {
+            type Return = Result<T, Error<$err>>;
+            fn specialized(&self) -> Self::Return {
+                match self.0.to_str() {
+                    None => Err(Error::Utf8(self.0.to_os_string())),
+                    Some($s) => {$($body)*}.map_err(Error::ParseErr),
                 }
             }
         }
@@ -98,6 +217,33 @@ macro_rules! specialize {
             }
         }
     };
+    (impl ($($and:tt)+) $name:ident for $from_ty:path {
+        fn from_bytes($s:ident: &[u8]) -> Result<T, $err:ty> {$($body:tt)*}
+    }) => {
+        specialize! {
+            impl ($($and)+) $name for $from_ty {
+                fn specialized(&self) -> Result<T, $err> {
+                    let bytes = os_str_to_bytes(self.0);
+                    let $s = bytes.as_slice();
+                    $($body)*
+                }
+            }
+        }
+    };
+    // Like `from_bytes`, but borrows the `OsStr`'s bytes with zero copies instead of going via
+    // an owned `Vec<u8>`. Unix only: see `os_str_as_bytes`.
+    (impl ($($and:tt)+) $name:ident for $from_ty:path {
+        fn from_bytes_ref($s:ident: &[u8]) -> Result<T, $err:ty> {$($body:tt)*}
+    }) => {
+        specialize! {
+            impl ($($and)+) $name for $from_ty {
+                fn specialized(&self) -> Result<T, $err> {
+                    let $s = os_str_as_bytes(self.0);
+                    $($body)*
+                }
+            }
+        }
+    };
     (impl ($($and:tt)+) $name:ident for $from_ty:path {
         fn specialized(&$self:ident) -> Result<T, $err:ty> {$($body:tt)*}
     }) => {
@@ -105,6 +251,11 @@ macro_rules! specialize {
         pub trait $name {
             type Return;
             fn specialized(&self) -> Self::Return;
+            // This tier doesn't go via a UTF8 string, so there's nothing lossy about it: the
+            // lossy macro gets exactly the same conversion as the normal one.
+            fn specialized_lossy(&self) -> Self::Return {
+                self.specialized()
+            }
         }
 
         impl<'a, T: $from_ty> $name for $($and)+Wrap<'a, T> {
@@ -125,7 +276,7 @@ specialize! {
 
 specialize! {
     impl (&&) Specialize7 for TryFrom<&'a str> {
-        fn from_str(s: &str) -> Result<T, T::Error> {
+        fn from_str_ref(s: &str) -> Result<T, T::Error> {
             T::try_from(s)
         }
     }
@@ -140,23 +291,57 @@ specialize! {
 }
 
 specialize! {
-    impl (&&&&) Specialize5 for From<String> {
+    impl (&&&&) Specialize4 for From<&'a str> {
+        fn from_str_ref(s: &str) -> Result<T, Infallible> {
+            Ok(T::from(s))
+        }
+    }
+}
+
+// Ranked just above `From<&str>`: most types that implement `From<&str>` (e.g. `String`
+// itself) also implement `From<String>`, and the latter is the one that can support the lossy
+// macro (it doesn't borrow from the input), so it needs to be tried first.
+specialize! {
+    impl (&&&&&) Specialize5 for From<String> {
         fn from_str(s: &str) -> Result<T, Infallible> {
             Ok(T::from(s.to_string()))
         }
     }
 }
 
+// Byte oriented conversions sit above the UTF8 string tiers (they must win over e.g.
+// `From<String>` so non UTF8 input still converts) but below the `From<&OsStr>`/`From<OsString>`/
+// `From<&Path>` tiers, since those keep the platform's native representation.
+// The borrowed tiers (`TryFrom<&[u8]>`/`From<&[u8]>`) are Unix only, since producing a real
+// zero-copy `&[u8]` out of a Windows `OsStr` would require allocating, defeating the point.
+#[cfg(unix)]
 specialize! {
-    impl (&&&&&) Specialize4 for From<&'a str> {
-        fn from_str(s: &str) -> Result<T, Infallible> {
+    impl (&&&&&&) SpecializeBytes3 for TryFrom<&'a [u8]> {
+        fn from_bytes_ref(s: &[u8]) -> Result<T, T::Error> {
+            T::try_from(s)
+        }
+    }
+}
+
+specialize! {
+    impl (&&&&&&&) SpecializeBytes2 for From<Vec<u8>> {
+        fn from_bytes(s: &[u8]) -> Result<T, Infallible> {
+            Ok(T::from(s.to_vec()))
+        }
+    }
+}
+
+#[cfg(unix)]
+specialize! {
+    impl (&&&&&&&&) SpecializeBytes1 for From<&'a [u8]> {
+        fn from_bytes_ref(s: &[u8]) -> Result<T, Infallible> {
             Ok(T::from(s))
         }
     }
 }
 
 specialize! {
-    impl (&&&&&&) Specialize3 for From<OsString> {
+    impl (&&&&&&&&&) Specialize3 for From<OsString> {
         fn from_os_str(s: &OsStr) -> Result<T, Infallible> {
             Ok(T::from(s.to_os_string()))
         }
@@ -164,7 +349,7 @@ specialize! {
 }
 
 specialize! {
-    impl (&&&&&&&) Specialize2 for From<&'a Path> {
+    impl (&&&&&&&&&&) Specialize2 for From<&'a Path> {
         fn from_os_str(s: &OsStr) -> Result<T, Infallible> {
             Ok(T::from(Path::new(s)))
         }
@@ -172,13 +357,84 @@ specialize! {
 }
 
 specialize! {
-    impl (&&&&&&&&) Specialize1 for From<&'a OsStr> {
+    impl (&&&&&&&&&&&) Specialize1 for From<&'a OsStr> {
         fn from_os_str(s: &OsStr) -> Result<T, Infallible> {
             Ok(T::from(s))
         }
     }
 }
 
+// `camino`'s `Utf8Path`/`Utf8PathBuf` can't be built generically from any `From<&OsStr>` style
+// bound since they guarantee valid UTF8, so they get a dedicated tier that reuses the same UTF8
+// check as the `String`/`&str` tiers instead.
+#[cfg(feature = "camino")]
+#[doc(hidden)]
+pub trait SpecializeCamino {
+    type Return;
+    fn specialized(&self) -> Self::Return;
+    fn specialized_lossy(&self) -> Self::Return {
+        self.specialized()
+    }
+}
+
+#[cfg(feature = "camino")]
+impl<'a> SpecializeCamino for &&&&&&&&&&&&Wrap<'a, Utf8PathBuf> {
+    type Return = Result<Utf8PathBuf, Error<Infallible>>;
+    fn specialized(&self) -> Self::Return {
+        match self.0.to_str() {
+            None => Err(Error::Utf8(self.0.to_os_string())),
+            Some(s) => Ok(Utf8PathBuf::from(s)),
+        }
+    }
+    fn specialized_lossy(&self) -> Self::Return {
+        Ok(Utf8PathBuf::from(self.0.to_string_lossy().into_owned()))
+    }
+}
+
+// `&Utf8Path` keeps its strict behaviour even under the lossy macro: `to_string_lossy` only
+// borrows for free when the input is already valid UTF8, so the replaced-invalid-bytes case
+// would need to allocate a `String` that doesn't live long enough to borrow a `&'a Utf8Path`
+// from, the same reason the borrowed byte tiers are Unix-only.
+#[cfg(feature = "camino")]
+impl<'a> SpecializeCamino for &&&&&&&&&&&&Wrap<'a, &'a Utf8Path> {
+    type Return = Result<&'a Utf8Path, Error<Infallible>>;
+    fn specialized(&self) -> Self::Return {
+        match self.0.to_str() {
+            None => Err(Error::Utf8(self.0.to_os_string())),
+            Some(s) => Ok(Utf8Path::new(s)),
+        }
+    }
+}
+
+// `Cow<'a, str>` implements both `From<&'a str>` and `From<String>`, so left to the generic
+// tiers it would resolve via `From<String>` (ranked above `From<&str>`, see above) and always
+// allocate, even when the input is already valid UTF8. A dedicated, highest priority tier lets
+// it borrow zero-copy in that case and only allocate (matching the lossy tiers' behaviour under
+// `from_os_str_lossy!`) when the input isn't valid UTF8.
+#[doc(hidden)]
+pub trait SpecializeCowStr {
+    type Return;
+    fn specialized(&self) -> Self::Return;
+    fn specialized_lossy(&self) -> Self::Return;
+}
+
+impl<'a> SpecializeCowStr for &&&&&&&&&&&&&Wrap<'a, Cow<'a, str>> {
+    type Return = Result<Cow<'a, str>, Error<Infallible>>;
+    fn specialized(&self) -> Self::Return {
+        match self.0.to_str() {
+            None => Err(Error::Utf8(self.0.to_os_string())),
+            Some(s) => Ok(Cow::Borrowed(s)),
+        }
+    }
+    fn specialized_lossy(&self) -> Self::Return {
+        Ok(self.0.to_string_lossy())
+    }
+}
+
+// `Cow<'a, OsStr>` needs no dedicated tier: it implements `From<&'a OsStr>` (ranked above
+// `From<OsString>`, see `Specialize1`/`Specialize3`), which already borrows zero-copy and wins
+// over the owned conversion, so the generic tiers give the right answer for free.
+
 /// Convert an `&OsStr` to another more usefull type
 /// There are lots of ways to do that and this will pick the best via
 /// [autoref based specialization](https://lukaskalbertodt.github.io/2019/12/05/generalized-autoref-based-specialization.html)
@@ -201,10 +457,54 @@ specialize! {
 /// assert_eq!(int, 123);
 /// # Ok(())}
 /// ```
+///
+/// The left hand side isn't limited to a variable: any expression producing an `&OsStr` works,
+/// e.g. `try_from_os_str!(args.next().unwrap() as PathBuf)`.
 #[macro_export]
 macro_rules! try_from_os_str {
-    ($name:ident as $typ:ty) => {
-        (&&&&&&&&Wrap::<$typ>::new($name)).specialized()
+    (@munch [$($name:tt)*] as $typ:ty) => {
+        (&&&&&&&&&&&&&Wrap::<$typ>::new($($name)*)).specialized()
+    };
+    (@munch [$($name:tt)*] $head:tt $($rest:tt)*) => {
+        $crate::try_from_os_str!(@munch [$($name)* $head] $($rest)*)
+    };
+    ($($all:tt)+) => {
+        $crate::try_from_os_str!(@munch [] $($all)+)
+    };
+}
+
+/// Convert an `&OsStr` to another more usefull type, accepting lossy UTF8 instead of failing
+/// There are lots of ways to do that and this will pick the best via
+/// [autoref based specialization](https://lukaskalbertodt.github.io/2019/12/05/generalized-autoref-based-specialization.html)
+/// This works just like [`try_from_os_str!`], except that the `FromStr`/`From<String>` tiers
+/// (and the `Cow<str>` and camino `Utf8PathBuf` targets) are fed the output of
+/// `to_string_lossy()` (replacing invalid UTF8 with `U+FFFD`) instead of returning
+/// [`Error::Utf8`]. The `TryFrom<&str>`/`From<&str>` tiers and `&Utf8Path` borrow from the
+/// input, so they still return [`Error::Utf8`] for non UTF8 input. Byte, `OsStr` and `Path`
+/// based tiers are already lossless and behave exactly as they do with `try_from_os_str!`.
+/// The result can still be an `Err` if parsing the (possibly lossy) string fails.
+/// ```
+/// # #[macro_use] extern crate from_os_str;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>>{
+/// use from_os_str::*;
+/// use std::ffi::OsStr;
+/// let os_str = OsStr::new("123");
+/// let string = from_os_str_lossy!(os_str as String)?;
+/// assert_eq!(string, "123".to_string());
+/// # Ok(())}
+/// ```
+///
+/// Like [`try_from_os_str!`], the left hand side can be any expression producing an `&OsStr`.
+#[macro_export]
+macro_rules! from_os_str_lossy {
+    (@munch [$($name:tt)*] as $typ:ty) => {
+        (&&&&&&&&&&&&&Wrap::<$typ>::new($($name)*)).specialized_lossy()
+    };
+    (@munch [$($name:tt)*] $head:tt $($rest:tt)*) => {
+        $crate::from_os_str_lossy!(@munch [$($name)* $head] $($rest)*)
+    };
+    ($($all:tt)+) => {
+        $crate::from_os_str_lossy!(@munch [] $($all)+)
     };
 }
 
@@ -249,6 +549,16 @@ mod tests {
         let int = try_from_os_str!(os_str as u8).unwrap();
         assert_eq!(int, 123);
 
+        let bytes = try_from_os_str!(os_str as Vec<u8>).unwrap();
+        assert_eq!(bytes, b"123".to_vec());
+
+        // `Cow` targets borrow zero-copy when the input is valid UTF8
+        let cow_str = try_from_os_str!(os_str as std::borrow::Cow<str>).unwrap();
+        assert!(matches!(cow_str, std::borrow::Cow::Borrowed("123")));
+        let cow_os_str = try_from_os_str!(os_str as std::borrow::Cow<OsStr>).unwrap();
+        assert!(matches!(cow_os_str, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(cow_os_str, os_str);
+
         // test priority works
         let foo = try_from_os_str!(os_str as Foo);
         assert_eq!(foo, Ok(Foo("OS: 123".to_owned())));
@@ -267,14 +577,114 @@ mod tests {
         let path = try_from_os_str!(os_str as PathBuf).unwrap();
         assert_eq!(path, Path::new(os_str));
         let str = try_from_os_str!(os_str as &str);
-        assert_eq!(str, Err(Error::Utf8));
+        assert_eq!(str, Err(Error::Utf8(os_str.to_os_string())));
         let string = try_from_os_str!(os_str as String);
-        assert_eq!(string, Err(Error::Utf8));
+        assert_eq!(string, Err(Error::Utf8(os_str.to_os_string())));
         let int = try_from_os_str!(os_str as u8);
-        assert_eq!(int, Err(Error::Utf8));
+        assert_eq!(int, Err(Error::Utf8(os_str.to_os_string())));
+
+        // byte conversions stay lossless even though the input isn't valid UTF8
+        let bytes = try_from_os_str!(os_str as Vec<u8>).unwrap();
+        assert_eq!(bytes, os_str.as_bytes().to_vec());
+
+        // `Cow<str>` rejects non UTF8 just like `&str`/`String`, but `Cow<OsStr>` always borrows
+        let cow_str = try_from_os_str!(os_str as std::borrow::Cow<str>);
+        assert_eq!(cow_str, Err(Error::Utf8(os_str.to_os_string())));
+        let cow_os_str = try_from_os_str!(os_str as std::borrow::Cow<OsStr>).unwrap();
+        assert!(matches!(cow_os_str, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(cow_os_str, os_str);
 
         // test priority works
         let foo = try_from_os_str!(os_str as Foo);
         assert_eq!(foo, Ok(Foo("OS: ��".to_owned())));
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn it_round_trips_bytes_through_os_string_from_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let os_str = OsStr::from_bytes(&[0xff, 0xff]);
+        let bytes = os_str_to_bytes(os_str);
+        assert_eq!(os_string_from_bytes(&bytes), os_str);
+    }
+
+    #[test]
+    #[cfg(feature = "camino")]
+    fn it_works_with_camino() {
+        use camino::{Utf8Path, Utf8PathBuf};
+
+        let os_str = OsStr::new("123");
+        let path = try_from_os_str!(os_str as &Utf8Path).unwrap();
+        assert_eq!(path, Utf8Path::new("123"));
+        let path_buf = try_from_os_str!(os_str as Utf8PathBuf).unwrap();
+        assert_eq!(path_buf, Utf8PathBuf::from("123"));
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "camino"))]
+    fn it_rejects_non_utf8_camino() {
+        use camino::{Utf8Path, Utf8PathBuf};
+        use std::os::unix::ffi::OsStrExt;
+
+        let os_str = OsStr::from_bytes(&[0xff, 0xff]);
+        let path = try_from_os_str!(os_str as &Utf8Path);
+        assert_eq!(path, Err(Error::Utf8(os_str.to_os_string())));
+        let path_buf = try_from_os_str!(os_str as Utf8PathBuf);
+        assert_eq!(path_buf, Err(Error::Utf8(os_str.to_os_string())));
+    }
+
+    #[test]
+    fn it_works_lossy() {
+        let os_str = OsStr::new("123");
+        let string = from_os_str_lossy!(os_str as String).unwrap();
+        assert_eq!(string, "123".to_string());
+        let int = from_os_str_lossy!(os_str as u8).unwrap();
+        assert_eq!(int, 123);
+
+        // byte/OsStr/Path tiers are unaffected by the lossy macro
+        let path = from_os_str_lossy!(os_str as PathBuf).unwrap();
+        assert_eq!(&path, Path::new("123"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn it_works_lossy_with_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+        let os_str = OsStr::from_bytes(&[0xff, 0xff]);
+
+        // the strict macro rejects this input...
+        let string = try_from_os_str!(os_str as String);
+        assert_eq!(string, Err(Error::Utf8(os_str.to_os_string())));
+
+        // ...but the lossy macro replaces the invalid bytes instead of failing
+        let string = from_os_str_lossy!(os_str as String).unwrap();
+        assert_eq!(string, "\u{FFFD}\u{FFFD}".to_string());
+
+        // parse errors on the (possibly lossy) string still surface
+        let int = from_os_str_lossy!(os_str as u8);
+        assert!(matches!(int, Err(Error::ParseErr(_))));
+
+        // byte/OsStr/Path tiers stay lossless
+        let path = from_os_str_lossy!(os_str as PathBuf).unwrap();
+        assert_eq!(path, Path::new(os_str));
+        let bytes = from_os_str_lossy!(os_str as Vec<u8>).unwrap();
+        assert_eq!(bytes, os_str.as_bytes().to_vec());
+
+        // `Cow<str>` allocates and replaces invalid bytes under the lossy macro
+        let cow_str = from_os_str_lossy!(os_str as std::borrow::Cow<str>).unwrap();
+        assert_eq!(cow_str, "\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn it_accepts_arbitrary_expressions() {
+        // the left hand side isn't limited to a bare variable
+        let strings = [OsString::from("123"), OsString::from("45")];
+        let mut iter = strings.iter();
+        let int = try_from_os_str!(iter.next().unwrap() as u8).unwrap();
+        assert_eq!(int, 123);
+
+        let int = from_os_str_lossy!(strings.last().unwrap() as u8).unwrap();
+        assert_eq!(int, 45);
+    }
 }
@@ -0,0 +1,97 @@
+// A minimal, pure Rust WTF-8 transform, used to losslessly turn a Windows `OsStr`
+// (which is ill-formed UTF-16) into bytes and back.
+// See https://simonsapin.github.io/wtf-8/ for the format this implements.
+
+// Encode UTF-16 code units, combining valid surrogate pairs into a single code point and
+// leaving unpaired surrogates as their raw code point value, then emit each as WTF-8 bytes.
+pub(crate) fn encode(units: impl Iterator<Item = u16>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut units = units.peekable();
+    while let Some(unit) = units.next() {
+        let code_point = match unit {
+            0xD800..=0xDBFF => match units.peek() {
+                Some(&low) if (0xDC00..=0xDFFF).contains(&low) => {
+                    units.next();
+                    0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00)
+                }
+                _ => u32::from(unit),
+            },
+            _ => u32::from(unit),
+        };
+        push_code_point(code_point, &mut bytes);
+    }
+    bytes
+}
+
+fn push_code_point(code_point: u32, bytes: &mut Vec<u8>) {
+    match code_point {
+        0..=0x7F => bytes.push(code_point as u8),
+        0x80..=0x7FF => {
+            bytes.push(0xC0 | (code_point >> 6) as u8);
+            bytes.push(0x80 | (code_point & 0x3F) as u8);
+        }
+        0x800..=0xFFFF => {
+            bytes.push(0xE0 | (code_point >> 12) as u8);
+            bytes.push(0x80 | ((code_point >> 6) & 0x3F) as u8);
+            bytes.push(0x80 | (code_point & 0x3F) as u8);
+        }
+        _ => {
+            bytes.push(0xF0 | (code_point >> 18) as u8);
+            bytes.push(0x80 | ((code_point >> 12) & 0x3F) as u8);
+            bytes.push(0x80 | ((code_point >> 6) & 0x3F) as u8);
+            bytes.push(0x80 | (code_point & 0x3F) as u8);
+        }
+    }
+}
+
+// Parse WTF-8 bytes back into UTF-16 code units, re-emitting surrogate pairs for any code
+// point outside the BMP.
+pub(crate) fn decode(bytes: &[u8]) -> Vec<u16> {
+    let mut units = Vec::new();
+    let mut bytes = bytes.iter().copied();
+    while let Some(b0) = bytes.next() {
+        let code_point = if b0 < 0x80 {
+            u32::from(b0)
+        } else if b0 & 0xE0 == 0xC0 {
+            let b1 = bytes.next().unwrap_or(0);
+            (u32::from(b0 & 0x1F) << 6) | u32::from(b1 & 0x3F)
+        } else if b0 & 0xF0 == 0xE0 {
+            let b1 = bytes.next().unwrap_or(0);
+            let b2 = bytes.next().unwrap_or(0);
+            (u32::from(b0 & 0x0F) << 12) | (u32::from(b1 & 0x3F) << 6) | u32::from(b2 & 0x3F)
+        } else {
+            let b1 = bytes.next().unwrap_or(0);
+            let b2 = bytes.next().unwrap_or(0);
+            let b3 = bytes.next().unwrap_or(0);
+            (u32::from(b0 & 0x07) << 18)
+                | (u32::from(b1 & 0x3F) << 12)
+                | (u32::from(b2 & 0x3F) << 6)
+                | u32::from(b3 & 0x3F)
+        };
+        if code_point >= 0x10000 {
+            let v = code_point - 0x10000;
+            units.push(0xD800 + (v >> 10) as u16);
+            units.push(0xDC00 + (v & 0x3FF) as u16);
+        } else {
+            units.push(code_point as u16);
+        }
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_valid_utf16() {
+        let units: Vec<u16> = "hello \u{1F600}".encode_utf16().collect();
+        assert_eq!(decode(&encode(units.iter().copied())), units);
+    }
+
+    #[test]
+    fn round_trips_unpaired_surrogate() {
+        let units = vec![0x0041, 0xD800, 0x0042];
+        assert_eq!(decode(&encode(units.iter().copied())), units);
+    }
+}